@@ -1,22 +1,30 @@
 use clap::Parser;
+use ethers::types::U256;
 use ethers::utils::{keccak256, hex};
 use rand::Rng;
+use regex::{RegexSet, RegexSetBuilder};
 use std::io::{self, Write};
 use rayon::prelude::*;
-use std::sync::Mutex;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::Instant;
 use num_cpus;
 
-const PROXY_BYTECODE: &str = "0x67363d3d37363d34f03d5260086018f3";
-lazy_static::lazy_static! {
-    static ref PROXY_BYTECODE_HASH: [u8; 32] = keccak256(hex::decode(&PROXY_BYTECODE[2..]).unwrap());
-}
-const FACTORY_ADDRESS: &str = "0x93FEC2C00BfE902F733B57c5a6CeeD7CD1384AE1";
+const DEFAULT_PROXY_BYTECODE: &str = "0x67363d3d37363d34f03d5260086018f3";
+const DEFAULT_FACTORY_ADDRESS: &str = "0x93FEC2C00BfE902F733B57c5a6CeeD7CD1384AE1";
 
 #[derive(Debug)]
 pub struct FindSaltOptions {
     creator: String,
     starts_with: Option<String>,
     ends_with: Option<String>,
+    pattern_set: Option<RegexSet>,
+    match_all_patterns: bool,
+    case_sensitive: bool,
+    base_salt: [u8; 32],
+    factory_bytes: Vec<u8>,
+    proxy_bytecode_hash: [u8; 32],
+    leading_zero_bytes: Option<usize>,
+    total_zero_bytes: Option<usize>,
     silent: bool,
     max_attempts: u64,
     parallel: bool,
@@ -28,13 +36,13 @@ pub struct SaltResult {
     address: String,
 }
 
-fn get_deployed(salt: &[u8]) -> String {
+fn get_deployed(salt: &[u8], factory_bytes: &[u8], proxy_bytecode_hash: &[u8; 32]) -> String {
     let mut packed = Vec::with_capacity(1 + 20 + 32 + 32);
     packed.extend_from_slice(&[0xff]);
-    packed.extend_from_slice(&hex::decode(&FACTORY_ADDRESS[2..]).unwrap());
+    packed.extend_from_slice(factory_bytes);
     packed.extend_from_slice(salt);
-    packed.extend_from_slice(&PROXY_BYTECODE_HASH[..]);
-    
+    packed.extend_from_slice(proxy_bytecode_hash);
+
     let encode1 = keccak256(packed);
     let proxy = format!("0x{}", hex::encode(&encode1[12..]));
     
@@ -47,57 +55,262 @@ fn get_deployed(salt: &[u8]) -> String {
     format!("0x{}", hex::encode(&encoded2[12..]))
 }
 
-fn is_valid_address(address: &str, starts_with: &Option<String>, ends_with: &Option<String>) -> bool {
-    if starts_with.is_none() && ends_with.is_none() {
-        return true;
+/// Computes the EIP-55 checksummed form of a 40-char lowercase hex address (no `0x`): for each
+/// hex digit, uppercase it if the corresponding nibble of `keccak256(ascii hex)` is `>= 8`.
+fn to_checksum_address(address_hex: &str) -> String {
+    let address_lower = address_hex.to_lowercase();
+    let hash = keccak256(address_lower.as_bytes());
+
+    address_lower
+        .char_indices()
+        .map(|(i, c)| {
+            if !c.is_ascii_alphabetic() {
+                return c;
+            }
+            let nibble = if i % 2 == 0 { hash[i / 2] >> 4 } else { hash[i / 2] & 0x0f };
+            if nibble >= 8 {
+                c.to_ascii_uppercase()
+            } else {
+                c
+            }
+        })
+        .collect()
+}
+
+fn is_valid_address(
+    address: &str,
+    starts_with: &Option<String>,
+    ends_with: &Option<String>,
+    pattern_set: &Option<RegexSet>,
+    match_all_patterns: bool,
+    case_sensitive: bool,
+) -> bool {
+    let compare_address = if case_sensitive {
+        format!("0x{}", to_checksum_address(&address[2..]))
+    } else {
+        address.to_lowercase()
+    };
+
+    let affix_ok = if starts_with.is_none() && ends_with.is_none() {
+        true
+    } else {
+        let cased = |s: &str| if case_sensitive { s.to_string() } else { s.to_lowercase() };
+        match (starts_with, ends_with) {
+            (Some(prefix), Some(suffix)) => {
+                compare_address.starts_with(&cased(prefix)) &&
+                compare_address.ends_with(&cased(suffix))
+            },
+            (Some(prefix), None) => compare_address.starts_with(&cased(prefix)),
+            (None, Some(suffix)) => compare_address.ends_with(&cased(suffix)),
+            (None, None) => true,
+        }
+    };
+
+    if !affix_ok {
+        return false;
     }
 
-    let address_lower = address.to_lowercase();
-    match (starts_with, ends_with) {
-        (Some(prefix), Some(suffix)) => {
-            address_lower.starts_with(&prefix.to_lowercase()) && 
-            address_lower.ends_with(&suffix.to_lowercase())
-        },
-        (Some(prefix), None) => address_lower.starts_with(&prefix.to_lowercase()),
-        (None, Some(suffix)) => address_lower.ends_with(&suffix.to_lowercase()),
-        (None, None) => true,
+    match pattern_set {
+        Some(set) => matches_pattern_set(&compare_address[2..], set, match_all_patterns),
+        None => true,
     }
 }
 
+fn matches_pattern_set(address_hex: &str, set: &RegexSet, match_all_patterns: bool) -> bool {
+    if match_all_patterns {
+        set.matches(address_hex).iter().count() == set.len()
+    } else {
+        set.is_match(address_hex)
+    }
+}
+
+/// Scores an address for the "crunch" gas-optimization mode: `(leading zero bytes, total zero
+/// bytes)` of the 20-byte address, counted from its raw bytes rather than hex nibbles.
+fn crunch_score(address: &str) -> (usize, usize) {
+    let bytes = hex::decode(&address[2..]).unwrap();
+    let leading = bytes.iter().take_while(|&&b| b == 0).count();
+    let total = bytes.iter().filter(|&&b| b == 0).count();
+    (leading, total)
+}
+
+fn meets_crunch_threshold(
+    score: (usize, usize),
+    leading_zero_bytes: Option<usize>,
+    total_zero_bytes: Option<usize>,
+) -> bool {
+    let (leading, total) = score;
+    leading_zero_bytes.map_or(true, |n| leading >= n) &&
+    total_zero_bytes.map_or(true, |n| total >= n)
+}
+
+fn is_crunch_mode(options: &FindSaltOptions) -> bool {
+    options.leading_zero_bytes.is_some() || options.total_zero_bytes.is_some()
+}
+
+/// Packs a `(leading, total)` crunch score into a single `u64`, leading zero bytes in the high
+/// bits, so that comparing (or `fetch_max`-ing) the packed values compares the tuple lexically.
+fn pack_crunch_score(score: (usize, usize)) -> u64 {
+    ((score.0 as u64) << 32) | (score.1 as u64)
+}
+
+fn is_match(options: &FindSaltOptions, address: &str) -> bool {
+    if is_crunch_mode(options) {
+        meets_crunch_threshold(crunch_score(address), options.leading_zero_bytes, options.total_zero_bytes)
+    } else {
+        is_valid_address(address, &options.starts_with, &options.ends_with, &options.pattern_set, options.match_all_patterns, options.case_sensitive)
+    }
+}
+
+/// Derives the 32-byte base salt a run walks from: a user-supplied `--seed` is left-padded into
+/// the salt, otherwise 32 bytes are drawn from OS entropy (and printed so the run can be redone).
+fn derive_base_salt(seed: &Option<String>) -> [u8; 32] {
+    match seed {
+        Some(seed_hex) => {
+            let bytes = hex::decode(seed_hex.trim_start_matches("0x")).expect("invalid --seed hex");
+            assert!(bytes.len() <= 32, "--seed must be at most 32 bytes");
+            let mut salt = [0u8; 32];
+            salt[32 - bytes.len()..].copy_from_slice(&bytes);
+            salt
+        }
+        None => rand::thread_rng().gen(),
+    }
+}
+
+/// Validates and decodes a `--factory` address: must be `0x` followed by exactly 40 hex chars.
+fn parse_factory_address(address: &str) -> Vec<u8> {
+    let stripped = address.strip_prefix("0x").expect("--factory must start with 0x");
+    let bytes = hex::decode(stripped).expect("--factory must be valid hex");
+    assert!(bytes.len() == 20, "--factory must encode a 20-byte address");
+    bytes
+}
+
+/// Validates and decodes `--proxy-bytecode`, then returns its `keccak256` hash for `get_deployed`.
+fn parse_proxy_bytecode_hash(bytecode: &str) -> [u8; 32] {
+    let stripped = bytecode.strip_prefix("0x").expect("--proxy-bytecode must start with 0x");
+    let bytes = hex::decode(stripped).expect("--proxy-bytecode must be valid hex");
+    assert!(!bytes.is_empty(), "--proxy-bytecode must not be empty");
+    keccak256(bytes)
+}
+
+/// Estimates the expected number of attempts to find a match: `16^nibbles`, where `nibbles` is
+/// the combined length of `starts_with`/`ends_with`, scaled by an extra factor of 2 per alphabetic
+/// hex nibble when `--case-sensitive` also constrains that nibble's EIP-55 case. Returns `None`
+/// when `--pattern` regexes are in play, since their difficulty isn't a simple nibble count.
+fn estimate_attempts(options: &FindSaltOptions) -> Option<f64> {
+    if options.pattern_set.is_some() {
+        return None;
+    }
+
+    let pattern_chars = options.starts_with.iter().chain(options.ends_with.iter())
+        .flat_map(|s| s.trim_start_matches("0x").chars());
+
+    Some(pattern_chars.fold(1f64, |difficulty, c| {
+        let case_factor = if options.case_sensitive && c.is_ascii_alphabetic() { 2.0 } else { 1.0 };
+        difficulty * 16.0 * case_factor
+    }))
+}
+
+fn format_eta(seconds: f64) -> String {
+    if !seconds.is_finite() || seconds < 0.0 {
+        return "unknown".to_string();
+    }
+    let secs = seconds as u64;
+    let (h, m, s) = (secs / 3600, (secs % 3600) / 60, secs % 60);
+    if h > 0 {
+        format!("{}h {}m {}s", h, m, s)
+    } else if m > 0 {
+        format!("{}m {}s", m, s)
+    } else {
+        format!("{}s", s)
+    }
+}
+
+fn print_progress(attempts: u64, expected_attempts: f64, start: Instant, address: &str) {
+    let elapsed = start.elapsed().as_secs_f64().max(1e-9);
+    let rate = attempts as f64 / elapsed;
+    let eta = (expected_attempts - attempts as f64).max(0.0) / rate;
+    print!("\rAttempt {} | {:.0} addr/s | ETA {} | {}", attempts, rate, format_eta(eta), address);
+    io::stdout().flush().unwrap();
+}
+
+fn print_summary(attempts: u64, start: Instant) {
+    let elapsed = start.elapsed().as_secs_f64().max(1e-9);
+    println!(
+        "Attempts: {} | Wall-clock: {} | Rate: {:.0} addr/s",
+        attempts,
+        format_eta(elapsed),
+        attempts as f64 / elapsed
+    );
+}
+
 pub fn find_salt(options: FindSaltOptions) -> Option<SaltResult> {
+    let expected_attempts = if is_crunch_mode(&options) {
+        f64::INFINITY
+    } else {
+        match estimate_attempts(&options) {
+            Some(expected_attempts) => {
+                if !options.silent {
+                    println!("Estimated attempts for a match: ~{:.0}", expected_attempts);
+                }
+                expected_attempts
+            }
+            None => {
+                if !options.silent {
+                    println!("Estimated attempts for a match: unknown (regex pattern difficulty not modeled)");
+                }
+                f64::INFINITY
+            }
+        }
+    };
+
     if options.parallel {
-        find_salt_parallel(options)
+        find_salt_parallel(options, expected_attempts)
     } else {
-        find_salt_sequential(options)
+        find_salt_sequential(options, expected_attempts)
     }
 }
 
-pub fn find_salt_sequential(options: FindSaltOptions) -> Option<SaltResult> {
+pub fn find_salt_sequential(options: FindSaltOptions, expected_attempts: f64) -> Option<SaltResult> {
     let mut attempts = 0;
-    let mut rng = rand::thread_rng();
+    let base = U256::from_big_endian(&options.base_salt);
+    let start = Instant::now();
+    let mut best_score = (0usize, 0usize);
 
     while attempts < options.max_attempts {
+        let mut salt = [0u8; 32];
+        (base + U256::from(attempts)).to_big_endian(&mut salt);
         attempts += 1;
-        let salt: [u8; 32] = rng.gen();
-        
+
         let mut packed = Vec::new();
         packed.extend_from_slice(&hex::decode(&options.creator[2..]).unwrap());
         packed.extend_from_slice(&salt);
-        
+
         let hex_salt = keccak256(packed);
-        let address = get_deployed(&hex_salt);
+        let address = get_deployed(&hex_salt, &options.factory_bytes, &options.proxy_bytecode_hash);
 
         if !options.silent {
-            print!("\rAttempt {}: {}", attempts, address);
-            io::stdout().flush().unwrap();
+            print_progress(attempts, expected_attempts, start, &address);
+        }
+
+        if is_crunch_mode(&options) {
+            let score = crunch_score(&address);
+            if score > best_score {
+                best_score = score;
+                if !options.silent {
+                    println!(
+                        "\nNew best: {} leading / {} total zero bytes -- {} (salt 0x{})",
+                        score.0, score.1, address, hex::encode(salt)
+                    );
+                }
+            }
         }
 
-        if is_valid_address(&address, &options.starts_with, &options.ends_with) {
+        if is_match(&options, &address) {
             if !options.silent {
                 println!("\nFound matching address!");
                 println!("Salt: 0x{}", hex::encode(salt));
                 println!("Address: {}", address);
-                println!("Attempts: {}", attempts);
+                print_summary(attempts, start);
             }
             return Some(SaltResult {
                 salt: format!("0x{}", hex::encode(salt)),
@@ -107,51 +320,68 @@ pub fn find_salt_sequential(options: FindSaltOptions) -> Option<SaltResult> {
     }
 
     if !options.silent {
-        println!("\nNo matching address found after {} attempts", attempts);
+        println!();
+        print_summary(attempts, start);
+        println!("No matching address found");
     }
     None
 }
 
-pub fn find_salt_parallel(options: FindSaltOptions) -> Option<SaltResult> {
+pub fn find_salt_parallel(options: FindSaltOptions, expected_attempts: f64) -> Option<SaltResult> {
     let chunk_size = 10000;
     let num_threads = num_cpus::get();
     let attempts_per_thread = options.max_attempts / num_threads as u64;
-    let progress = Mutex::new(0u64);
-    
+    let progress = AtomicU64::new(0);
+    let best_crunch_score = AtomicU64::new(0);
+    let start = Instant::now();
+
     let creator_bytes = hex::decode(&options.creator[2..]).unwrap();
+    let base = U256::from_big_endian(&options.base_salt);
+    let stride = U256::from(num_threads);
 
-    (0..num_threads).into_par_iter()
-        .find_map_any(|_| {
+    let result = (0..num_threads).into_par_iter()
+        .find_map_any(|thread_index| {
             let mut attempts = 0;
-            let mut rng = rand::thread_rng();
+            let mut counter = base + U256::from(thread_index);
             let mut packed = Vec::with_capacity(20 + 32);
             packed.extend_from_slice(&creator_bytes);
             packed.resize(packed.len() + 32, 0);
 
             while attempts < attempts_per_thread {
                 for _ in 0..chunk_size {
-                    rng.fill(&mut packed[creator_bytes.len()..]);
-                    
+                    counter.to_big_endian(&mut packed[creator_bytes.len()..]);
+                    counter += stride;
+
                     let hex_salt = keccak256(&packed);
-                    let address = get_deployed(&hex_salt);
+                    let address = get_deployed(&hex_salt, &options.factory_bytes, &options.proxy_bytecode_hash);
 
                     attempts += 1;
 
                     if !options.silent {
-                        let mut total = progress.lock().unwrap();
-                        *total += 1;
-                        if *total % 1000 == 0 {
-                            print!("\rAttempt {}", total);
-                            io::stdout().flush().unwrap();
+                        let total = progress.fetch_add(1, Ordering::Relaxed) + 1;
+                        if total % 1000 == 0 {
+                            print_progress(total, expected_attempts, start, &address);
                         }
                     }
 
-                    if is_valid_address(&address, &options.starts_with, &options.ends_with) {
+                    if is_crunch_mode(&options) {
+                        let score = crunch_score(&address);
+                        let packed_score = pack_crunch_score(score);
+                        if best_crunch_score.fetch_max(packed_score, Ordering::Relaxed) < packed_score {
+                            if !options.silent {
+                                println!(
+                                    "\nNew best: {} leading / {} total zero bytes -- {} (salt 0x{})",
+                                    score.0, score.1, address, hex::encode(&packed[creator_bytes.len()..])
+                                );
+                            }
+                        }
+                    }
+
+                    if is_match(&options, &address) {
                         if !options.silent {
                             println!("\nFound matching address!");
                             println!("Salt: 0x{}", hex::encode(&packed[creator_bytes.len()..]));
                             println!("Address: {}", address);
-                            println!("Attempts: {}", attempts);
                         }
                         return Some(SaltResult {
                             salt: format!("0x{}", hex::encode(&packed[creator_bytes.len()..])),
@@ -161,7 +391,15 @@ pub fn find_salt_parallel(options: FindSaltOptions) -> Option<SaltResult> {
                 }
             }
             None
-        })
+        });
+
+    if !options.silent {
+        print_summary(progress.load(Ordering::Relaxed), start);
+        if result.is_none() {
+            println!("No matching address found");
+        }
+    }
+    result
 }
 
 #[derive(Parser, Debug)]
@@ -175,7 +413,45 @@ struct Args {
     
     #[arg(short, long)]
     ends_with: Option<String>,
-    
+
+    /// Regex pattern to match against the 40-char hex address (no `0x`). Can be repeated to
+    /// supply several alternatives, e.g. `--pattern dead.*beef --pattern ^1337`.
+    #[arg(long = "pattern")]
+    patterns: Vec<String>,
+
+    /// Require every `--pattern` to match instead of just one of them.
+    #[arg(long = "match-all", default_value_t = false)]
+    match_all_patterns: bool,
+
+    /// Match `starts-with`/`ends-with`/`--pattern` against the EIP-55 checksummed address
+    /// instead of the raw lowercase hex, allowing mixed-case vanity like `0xDeAdBeeF`.
+    #[arg(long = "case-sensitive", default_value_t = false)]
+    case_sensitive: bool,
+
+    /// Hex seed (up to 32 bytes) the search walks from as an incrementing counter, instead of
+    /// drawing a fresh random salt per attempt. Omit to seed from OS entropy; the seed used is
+    /// always printed so a run can be reproduced.
+    #[arg(long)]
+    seed: Option<String>,
+
+    /// CREATE3 factory address to deploy through, in place of the default lifi factory.
+    #[arg(long, default_value_t = DEFAULT_FACTORY_ADDRESS.to_string())]
+    factory: String,
+
+    /// Proxy init bytecode the factory deploys via CREATE2, in place of the default minimal proxy.
+    #[arg(long, default_value_t = DEFAULT_PROXY_BYTECODE.to_string())]
+    proxy_bytecode: String,
+
+    /// "Crunch" mode: require at least this many leading `0x00` bytes in the deployed address,
+    /// to minimize calldata gas. Replaces `starts-with`/`ends-with`/`--pattern` matching.
+    #[arg(long)]
+    leading: Option<usize>,
+
+    /// "Crunch" mode: require at least this many total `0x00` bytes in the deployed address.
+    /// Can be combined with `--leading`.
+    #[arg(long)]
+    total: Option<usize>,
+
     #[arg(short, long, default_value_t = u64::MAX)]
     max_attempts: u64,
     
@@ -190,11 +466,36 @@ fn main() {
     let args = Args::parse();
     
     let starts_with = args.starts_with.map(|s| format!("0x{}", s));
-    
+
+    let pattern_set = if args.patterns.is_empty() {
+        None
+    } else {
+        Some(
+            RegexSetBuilder::new(&args.patterns)
+                .case_insensitive(!args.case_sensitive)
+                .build()
+                .expect("invalid --pattern regex"),
+        )
+    };
+
+    let base_salt = derive_base_salt(&args.seed);
+    println!("Seed: 0x{}", hex::encode(base_salt));
+
+    let factory_bytes = parse_factory_address(&args.factory);
+    let proxy_bytecode_hash = parse_proxy_bytecode_hash(&args.proxy_bytecode);
+
     if let Some(result) = find_salt(FindSaltOptions {
         creator: args.creator,
         starts_with,
         ends_with: args.ends_with,
+        pattern_set,
+        match_all_patterns: args.match_all_patterns,
+        case_sensitive: args.case_sensitive,
+        base_salt,
+        factory_bytes,
+        proxy_bytecode_hash,
+        leading_zero_bytes: args.leading,
+        total_zero_bytes: args.total,
         max_attempts: args.max_attempts,
         silent: args.silent,
         parallel: args.parallel,
@@ -202,3 +503,54 @@ fn main() {
         println!("Found result - Salt: {}, Address: {}", result.salt, result.address);
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn checksum_address_matches_eip55_vector() {
+        // https://eips.ethereum.org/EIPS/eip-55
+        assert_eq!(
+            to_checksum_address("5aaeb6053f3e94c9b9a09f33669435e7ef1beaed"),
+            "5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAed"
+        );
+    }
+
+    #[test]
+    fn get_deployed_matches_known_create3_address() {
+        let factory_bytes = parse_factory_address(DEFAULT_FACTORY_ADDRESS);
+        let proxy_bytecode_hash = parse_proxy_bytecode_hash(DEFAULT_PROXY_BYTECODE);
+        let salt = [0u8; 32];
+
+        assert_eq!(
+            get_deployed(&salt, &factory_bytes, &proxy_bytecode_hash),
+            "0x9165aba9710f6c5945daf2aa002d653115723ec7"
+        );
+    }
+
+    #[test]
+    fn crunch_score_counts_leading_and_total_zero_bytes() {
+        assert_eq!(
+            crunch_score("0x0000001100000000000000000000000000000011"),
+            (3, 18)
+        );
+    }
+
+    #[test]
+    fn derive_base_salt_left_pads_seed() {
+        let mut expected = [0u8; 32];
+        expected[30] = 0x12;
+        expected[31] = 0x34;
+        assert_eq!(derive_base_salt(&Some("0x1234".to_string())), expected);
+    }
+
+    #[test]
+    fn matches_pattern_set_any_vs_all() {
+        let set = RegexSetBuilder::new(["dead", "beef"]).build().unwrap();
+        assert!(matches_pattern_set("0000deadbeef0000", &set, false));
+        assert!(matches_pattern_set("0000deadbeef0000", &set, true));
+        assert!(matches_pattern_set("0000dead00000000", &set, false));
+        assert!(!matches_pattern_set("0000dead00000000", &set, true));
+    }
+}